@@ -1,12 +1,13 @@
-use serde::{Deserialize, Serialize};
+use std::{error::Error, fmt, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use ::chrono::{DateTime, Utc};
+use nonmax::NonMaxU32;
 use serde_with::*;
 
 #[cfg(feature = "builder")]
 use crate::Builder;
-#[cfg(feature = "builder")]
-use log::warn;
 // ======== Structures =========
 #[serde_as]
 #[skip_serializing_none]
@@ -49,18 +50,42 @@ pub struct EmbedThumbnail {
     /// Proxied url of the thumbnail
     pub proxy_url: Option<String>,
     /// Height of the image
-    pub height: Option<u32>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_nonmax_u32",
+        deserialize_with = "deserialize_nonmax_u32"
+    )]
+    height: Option<NonMaxU32>,
     /// Width of the image
-    pub width: Option<u32>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_nonmax_u32",
+        deserialize_with = "deserialize_nonmax_u32"
+    )]
+    width: Option<NonMaxU32>,
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Default)]
 /// Representing video information in an [`Embed`]
 pub struct EmbedVideo {
     url: String,
     proxy_url: String,
-    height: i32,
-    witdh: i32,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_nonmax_u32",
+        deserialize_with = "deserialize_nonmax_u32"
+    )]
+    height: Option<NonMaxU32>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_nonmax_u32",
+        deserialize_with = "deserialize_nonmax_u32"
+    )]
+    width: Option<NonMaxU32>,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Default)]
@@ -69,8 +94,43 @@ pub struct EmbedImage {
     pub url: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub proxy_url: Option<String>,
-    pub height: i32,
-    pub width: i32,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_nonmax_u32",
+        deserialize_with = "deserialize_nonmax_u32"
+    )]
+    height: Option<NonMaxU32>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_nonmax_u32",
+        deserialize_with = "deserialize_nonmax_u32"
+    )]
+    width: Option<NonMaxU32>,
+}
+
+/// Serializes an [`Option<NonMaxU32>`] as an ordinary nullable integer.
+///
+/// Hand-rolled instead of deriving `Serialize`/`Deserialize` on `NonMaxU32`
+/// itself, since `nonmax`'s serde support lives behind its own optional
+/// `serde` feature that this crate doesn't enable.
+fn serialize_nonmax_u32<S>(value: &Option<NonMaxU32>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    value.map(u32::from).serialize(serializer)
+}
+
+/// Deserializes a nullable Discord dimension field into an
+/// [`Option<NonMaxU32>`], treating an incoming `u32::MAX` the same as a
+/// missing value instead of failing to deserialize.
+fn deserialize_nonmax_u32<'de, D>(deserializer: D) -> Result<Option<NonMaxU32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Option<u32> = Option::deserialize(deserializer)?;
+    Ok(value.and_then(NonMaxU32::new))
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
@@ -121,6 +181,15 @@ pub struct EmbedField {
 pub struct EmbedBuilder {
     obj: Embed,
 }
+
+#[cfg(feature = "builder")]
+impl From<Embed> for EmbedBuilder {
+    /// Resume editing an existing [`Embed`], e.g. one fetched from a message
+    /// or received from an interaction.
+    fn from(embed: Embed) -> Self {
+        Self { obj: embed }
+    }
+}
 #[derive(Clone, Copy, Debug, PartialEq)]
 /// Representing RGB colors.
 ///
@@ -162,6 +231,86 @@ impl From<Color> for u32 {
     }
 }
 
+impl Color {
+    /// Discord's brand "blurple"
+    pub const BLURPLE: Color = Color {
+        red: 0x58,
+        green: 0x65,
+        blue: 0xF2,
+    };
+    /// Discord's brand green
+    pub const GREEN: Color = Color {
+        red: 0x57,
+        green: 0xF2,
+        blue: 0x87,
+    };
+    /// Discord's brand red, used for destructive/error states
+    pub const RED: Color = Color {
+        red: 0xED,
+        green: 0x42,
+        blue: 0x45,
+    };
+    /// Discord's brand gold, used for warning states
+    pub const GOLD: Color = Color {
+        red: 0xF1,
+        green: 0xC4,
+        blue: 0x0F,
+    };
+    /// Discord's brand dark gold
+    pub const DARK_GOLD: Color = Color {
+        red: 0xC2,
+        green: 0x7C,
+        blue: 0x0E,
+    };
+    /// A dark grey that reads as "dark mode" without being pure black
+    pub const DARK_BUT_NOT_BLACK: Color = Color {
+        red: 0x2C,
+        green: 0x2F,
+        blue: 0x33,
+    };
+}
+
+impl FromStr for Color {
+    type Err = ColorParseError;
+
+    /// Parses a `#RRGGBB` or `RRGGBB` hex string into a [`Color`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        if hex.len() != 6 {
+            return Err(ColorParseError::InvalidLength);
+        }
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(ColorParseError::InvalidDigit);
+        }
+        let value = u32::from_str_radix(hex, 16).map_err(|_| ColorParseError::InvalidDigit)?;
+        Ok(Color::from(value))
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Errors when parsing a [`Color`] from a hex string
+pub enum ColorParseError {
+    /// The string was not 6 hex digits long (ignoring a leading `#`)
+    InvalidLength,
+    /// The string contained a character that isn't a valid hex digit
+    InvalidDigit,
+}
+
+impl fmt::Display for ColorParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ColorParseError::InvalidLength => {
+                write!(f, "Color hex string must be 6 digits long!")
+            }
+            ColorParseError::InvalidDigit => {
+                write!(f, "Color hex string contains a non-hex digit!")
+            }
+        }
+    }
+}
+
+impl Error for ColorParseError {}
+
 impl Default for Embed {
     fn default() -> Self {
         Self {
@@ -185,10 +334,6 @@ impl EmbedBuilder {
     /// Set the title of this embed
     pub fn title(mut self, title: impl ToString) -> Self {
         let t = title.to_string();
-        // wish this could be checked at compile time :(
-        if t.len() > 256 {
-            panic!("Embed title length is more than 256 characters.")
-        }
         self.obj.title = Some(t);
         self
     }
@@ -248,29 +393,152 @@ impl EmbedBuilder {
                 self.obj.fields = nf;
             }
             Some(ref mut f) => {
-                if f.len() >= 25 {
-                    warn!("Field limit reached. Ignoring");
-                } else {
-                    f.push(field);
-                }
+                f.push(field);
             }
         }
         self
     }
+}
+
+/// The maximum combined character count Discord allows across an [`Embed`]'s
+/// title, description, field names/values, footer text and author name.
+#[cfg(feature = "builder")]
+const EMBED_TOTAL_LENGTH_LIMIT: usize = 6000;
 
-    #[deprecated(since = "0.1.9", note = "Use the `build()` function instead")]
-    /// Build the embed. You can't use the function after this anymore
-    pub fn finish(self) -> Embed {
-        self.obj
+#[cfg(feature = "builder")]
+#[derive(Clone, Debug)]
+/// Errors when building an [`Embed`]
+pub enum EmbedConversionError {
+    /// The title is longer than 256 characters
+    TitleTooLong,
+    /// The description is longer than 4096 characters
+    DescriptionTooLong,
+    /// A field's name is longer than 256 characters
+    FieldNameTooLong,
+    /// A field's value is longer than 1024 characters
+    FieldValueTooLong,
+    /// The footer text is longer than 2048 characters
+    FooterTextTooLong,
+    /// The author name is longer than 256 characters
+    AuthorNameTooLong,
+    /// More than 25 fields were added
+    TooManyFields,
+    /// The combined length of the title, description, every field's name and
+    /// value, the footer text and the author name exceeds Discord's total
+    /// character budget of 6000.
+    TotalLengthExceeded {
+        /// The combined character count that was supplied
+        got: usize,
+        /// The maximum combined character count allowed
+        max: usize,
+    },
+}
+
+#[cfg(feature = "builder")]
+impl fmt::Display for EmbedConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EmbedConversionError::TitleTooLong => {
+                write!(f, "Embed title is longer than 256 characters!")
+            }
+            EmbedConversionError::DescriptionTooLong => {
+                write!(f, "Embed description is longer than 4096 characters!")
+            }
+            EmbedConversionError::FieldNameTooLong => {
+                write!(f, "Embed field name is longer than 256 characters!")
+            }
+            EmbedConversionError::FieldValueTooLong => {
+                write!(f, "Embed field value is longer than 1024 characters!")
+            }
+            EmbedConversionError::FooterTextTooLong => {
+                write!(f, "Embed footer text is longer than 2048 characters!")
+            }
+            EmbedConversionError::AuthorNameTooLong => {
+                write!(f, "Embed author name is longer than 256 characters!")
+            }
+            EmbedConversionError::TooManyFields => {
+                write!(f, "Embed contains more than 25 fields!")
+            }
+            EmbedConversionError::TotalLengthExceeded { got, max } => {
+                write!(
+                    f,
+                    "Embed's combined character count ({}) exceeds the maximum of {}!",
+                    got, max
+                )
+            }
+        }
     }
 }
 
+#[cfg(feature = "builder")]
+impl Error for EmbedConversionError {}
+
 #[cfg(feature = "builder")]
 impl Builder<Embed> for EmbedBuilder {
-    type Error = std::convert::Infallible;
+    type Error = EmbedConversionError;
 
     fn build(self) -> Result<Embed, Self::Error> {
-        Ok(self.obj)
+        let obj = self.obj;
+
+        if let Some(ref title) = obj.title {
+            if title.chars().count() > 256 {
+                return Err(EmbedConversionError::TitleTooLong);
+            }
+        }
+        if let Some(ref description) = obj.description {
+            if description.chars().count() > 4096 {
+                return Err(EmbedConversionError::DescriptionTooLong);
+            }
+        }
+        if let Some(ref footer) = obj.footer {
+            if footer.text.chars().count() > 2048 {
+                return Err(EmbedConversionError::FooterTextTooLong);
+            }
+        }
+        if let Some(ref author) = obj.author {
+            if let Some(ref name) = author.name {
+                if name.chars().count() > 256 {
+                    return Err(EmbedConversionError::AuthorNameTooLong);
+                }
+            }
+        }
+        if let Some(ref fields) = obj.fields {
+            if fields.len() > 25 {
+                return Err(EmbedConversionError::TooManyFields);
+            }
+            for field in fields {
+                if field.name.chars().count() > 256 {
+                    return Err(EmbedConversionError::FieldNameTooLong);
+                }
+                if field.value.chars().count() > 1024 {
+                    return Err(EmbedConversionError::FieldValueTooLong);
+                }
+            }
+        }
+
+        let mut total = 0;
+        total += obj.title.as_ref().map_or(0, |t| t.chars().count());
+        total += obj.description.as_ref().map_or(0, |d| d.chars().count());
+        total += obj.footer.as_ref().map_or(0, |f| f.text.chars().count());
+        total += obj
+            .author
+            .as_ref()
+            .and_then(|a| a.name.as_ref())
+            .map_or(0, |n| n.chars().count());
+        if let Some(ref fields) = obj.fields {
+            for field in fields {
+                total += field.name.chars().count();
+                total += field.value.chars().count();
+            }
+        }
+        if total > EMBED_TOTAL_LENGTH_LIMIT {
+            return Err(EmbedConversionError::TotalLengthExceeded {
+                got: total,
+                max: EMBED_TOTAL_LENGTH_LIMIT,
+            });
+        }
+
+        Ok(obj)
     }
 }
 
@@ -288,18 +556,13 @@ impl EmbedFooter {
     /// Set the footers text
     pub fn text(mut self, text: impl ToString) -> Self {
         let t = text.to_string();
-        if t.len() > 2048 {
-            panic!("Footer text exceeded 2048 characters")
-        }
         self.text = t;
         self
     }
 
     /// Sets the url to the footer icon
-    pub fn icon_url(mut self, url: impl ToString) -> Self {
-        let n = url.to_string();
-
-        self.icon_url = Some(n);
+    pub fn icon_url(mut self, source: ImageSource) -> Self {
+        self.icon_url = Some(source.to_string());
         self
     }
 
@@ -326,9 +589,6 @@ impl EmbedField {
     /// Set the field name
     pub fn name(mut self, name: impl ToString) -> Self {
         let n = name.to_string();
-        if n.len() > 256 {
-            panic!("Field name is above 256 characters.")
-        }
         self.name = n;
         self
     }
@@ -336,10 +596,6 @@ impl EmbedField {
     /// Set the text of this field
     pub fn value(mut self, text: impl ToString) -> Self {
         let t = text.to_string();
-
-        if t.len() > 1024 {
-            panic!("Field value is above 1024 characters")
-        }
         self.value = t;
         self
     }
@@ -366,10 +622,8 @@ impl EmbedAuthor {
     }
 
     /// Add an icon to the embed
-    pub fn icon_url(mut self, url: impl ToString) -> Self {
-        let u = url.to_string();
-
-        self.icon_url = Some(u);
+    pub fn icon_url(mut self, source: ImageSource) -> Self {
+        self.icon_url = Some(source.to_string());
         self
     }
 
@@ -384,16 +638,15 @@ impl EmbedAuthor {
 
 impl EmbedThumbnail {
     /// Sets the URL of the thumbnail
-    pub fn url(mut self, url: impl ToString) -> Self {
-        let u = url.to_string();
-        self.url = Some(u);
+    pub fn url(mut self, source: ImageSource) -> Self {
+        self.url = Some(source.to_string());
         self
     }
 
     /// Sets a proxied url for the thumbnail
     pub fn proxy_url(mut self, url: impl ToString) -> Self {
         let u = url.to_string();
-        self.url = Some(u);
+        self.proxy_url = Some(u);
         self
     }
 
@@ -402,9 +655,229 @@ impl EmbedThumbnail {
         let x = width.into();
         let y = height.into();
 
-        self.height = Some(y);
-        self.width = Some(x);
+        self.height = NonMaxU32::new(y);
+        self.width = NonMaxU32::new(x);
+
+        self
+    }
+
+    /// Gets the height of the thumbnail
+    pub fn height(&self) -> Option<u32> {
+        self.height.map(u32::from)
+    }
+
+    /// Gets the width of the thumbnail
+    pub fn width(&self) -> Option<u32> {
+        self.width.map(u32::from)
+    }
+}
+
+impl EmbedImage {
+    /// Sets the URL of the image
+    pub fn url(mut self, source: ImageSource) -> Self {
+        self.url = source.to_string();
+        self
+    }
 
+    /// Sets a proxied url for the image
+    pub fn proxy_url(mut self, url: impl ToString) -> Self {
+        let u = url.to_string();
+        self.proxy_url = Some(u);
         self
     }
+
+    /// Sets the dimensions of the image
+    pub fn dimensions(mut self, height: impl Into<u32>, width: impl Into<u32>) -> Self {
+        let x = width.into();
+        let y = height.into();
+
+        self.height = NonMaxU32::new(y);
+        self.width = NonMaxU32::new(x);
+
+        self
+    }
+
+    /// Gets the height of the image
+    pub fn height(&self) -> Option<u32> {
+        self.height.map(u32::from)
+    }
+
+    /// Gets the width of the image
+    pub fn width(&self) -> Option<u32> {
+        self.width.map(u32::from)
+    }
+}
+
+impl EmbedVideo {
+    /// Gets the height of the video
+    pub fn height(&self) -> Option<u32> {
+        self.height.map(u32::from)
+    }
+
+    /// Gets the width of the video
+    pub fn width(&self) -> Option<u32> {
+        self.width.map(u32::from)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// A validated source for an embed's image, thumbnail or icon.
+///
+/// Can either point at a remote `http(s)://` URL, or reference a file
+/// uploaded alongside the interaction response via Discord's
+/// `attachment://<filename>` scheme.
+pub struct ImageSource(String);
+
+impl ImageSource {
+    /// Use a remote URL as the image source.
+    ///
+    /// The URL must start with `http://` or `https://`.
+    pub fn url(url: impl ToString) -> Result<Self, ImageSourceUrlError> {
+        let u = url.to_string();
+        if !u.starts_with("http://") && !u.starts_with("https://") {
+            return Err(ImageSourceUrlError);
+        }
+        Ok(Self(u))
+    }
+
+    /// Reference a file uploaded alongside this response, by its filename.
+    pub fn attachment(filename: impl ToString) -> Self {
+        Self(format!("attachment://{}", filename.to_string()))
+    }
+}
+
+impl fmt::Display for ImageSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Debug)]
+/// The supplied URL did not start with `http://` or `https://`
+pub struct ImageSourceUrlError;
+
+impl fmt::Display for ImageSourceUrlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Image source URL must start with `http://` or `https://`!"
+        )
+    }
+}
+
+impl Error for ImageSourceUrlError {}
+
+#[cfg(all(test, feature = "builder"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_rejects_title_over_256_chars() {
+        let embed = EmbedBuilder::default().title("a".repeat(257)).build();
+        assert!(matches!(embed, Err(EmbedConversionError::TitleTooLong)));
+    }
+
+    #[test]
+    fn build_accepts_title_at_256_chars() {
+        let embed = EmbedBuilder::default().title("a".repeat(256)).build();
+        assert!(embed.is_ok());
+    }
+
+    #[test]
+    fn build_rejects_more_than_25_fields() {
+        let mut builder = EmbedBuilder::default();
+        for i in 0..26 {
+            builder = builder.add_field(EmbedField::default().name(format!("f{}", i)).value("v"));
+        }
+        assert!(matches!(
+            builder.build(),
+            Err(EmbedConversionError::TooManyFields)
+        ));
+    }
+
+    #[test]
+    fn build_accepts_25_fields() {
+        let mut builder = EmbedBuilder::default();
+        for i in 0..25 {
+            builder = builder.add_field(EmbedField::default().name(format!("f{}", i)).value("v"));
+        }
+        assert!(builder.build().is_ok());
+    }
+
+    #[test]
+    fn build_accepts_total_length_at_6000_char_budget() {
+        let embed = EmbedBuilder::default()
+            .title("a".repeat(256))
+            .description("b".repeat(4096))
+            .footer(EmbedFooter::default().text("c".repeat(1024)))
+            .author(EmbedAuthor::default().name("d".repeat(256)))
+            .add_field(
+                EmbedField::default()
+                    .name("e".repeat(256))
+                    .value("f".repeat(112)),
+            )
+            .build();
+        assert!(embed.is_ok());
+    }
+
+    #[test]
+    fn build_rejects_total_length_over_6000_char_budget() {
+        let embed = EmbedBuilder::default()
+            .title("a".repeat(256))
+            .description("b".repeat(4096))
+            .footer(EmbedFooter::default().text("c".repeat(1024)))
+            .author(EmbedAuthor::default().name("d".repeat(256)))
+            .add_field(
+                EmbedField::default()
+                    .name("e".repeat(256))
+                    .value("f".repeat(113)),
+            )
+            .build();
+        assert!(matches!(
+            embed,
+            Err(EmbedConversionError::TotalLengthExceeded {
+                got: 6001,
+                max: 6000
+            })
+        ));
+    }
+
+    #[test]
+    fn image_source_accepts_http_and_https_urls() {
+        assert!(ImageSource::url("http://example.com/a.png").is_ok());
+        assert!(ImageSource::url("https://example.com/a.png").is_ok());
+    }
+
+    #[test]
+    fn image_source_rejects_non_http_scheme() {
+        assert!(ImageSource::url("ftp://example.com/a.png").is_err());
+    }
+
+    #[test]
+    fn image_source_attachment_uses_attachment_scheme() {
+        let source = ImageSource::attachment("avatar.png");
+        assert_eq!(source.to_string(), "attachment://avatar.png");
+    }
+
+    #[test]
+    fn color_from_str_parses_hex_with_and_without_hash() {
+        assert_eq!(Color::from_str("#5865F2").unwrap(), Color::BLURPLE);
+        assert_eq!(Color::from_str("5865F2").unwrap(), Color::BLURPLE);
+    }
+
+    #[test]
+    fn color_from_str_rejects_wrong_length() {
+        assert!(matches!(
+            Color::from_str("#5865F"),
+            Err(ColorParseError::InvalidLength)
+        ));
+    }
+
+    #[test]
+    fn color_from_str_rejects_non_hex_digits() {
+        assert!(matches!(
+            Color::from_str("#5865ZZ"),
+            Err(ColorParseError::InvalidDigit)
+        ));
+    }
 }
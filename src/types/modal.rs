@@ -99,6 +99,64 @@ impl fmt::Display for ModalConversionError {
 #[cfg(feature = "builder")]
 impl Error for ModalConversionError {}
 
+/// The data Discord sends back as part of an [`InteractionType::ModalSubmit`]
+/// interaction, once a user submits a [`Modal`].
+///
+/// This flattens the nested action-row/component tree Discord wraps the
+/// submitted values in, so a value can be looked up directly by the
+/// `custom_id` it was given when the modal was built.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ModalResponseData {
+    components: Vec<MessageComponent>,
+}
+
+impl ModalResponseData {
+    /// Get the submitted value for the component with the given `custom_id`,
+    /// if it was part of the submission.
+    pub fn get(&self, custom_id: &str) -> Option<&str> {
+        self.fields().find(|(id, _)| *id == custom_id).map(|(_, value)| value)
+    }
+
+    /// Get the submitted value for the component with the given `custom_id`,
+    /// returning a [`MissingFieldError`] if it wasn't part of the submission.
+    pub fn required(&self, custom_id: &str) -> Result<&str, MissingFieldError> {
+        self.get(custom_id)
+            .ok_or_else(|| MissingFieldError(custom_id.to_string()))
+    }
+
+    /// Iterate over every submitted `(custom_id, value)` pair, flattened out
+    /// of the action rows Discord wraps them in.
+    pub fn fields(&self) -> impl Iterator<Item = (&str, &str)> {
+        fn flatten<'a>(components: &'a [MessageComponent], out: &mut Vec<(&'a str, &'a str)>) {
+            for component in components {
+                match component {
+                    MessageComponent::ActionRow { components } => flatten(components, out),
+                    MessageComponent::TextInput { custom_id, value, .. } => {
+                        out.push((custom_id.as_str(), value.as_str()));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut out = Vec::new();
+        flatten(&self.components, &mut out);
+        out.into_iter()
+    }
+}
+
+#[derive(Clone, Debug)]
+/// A required field was missing from a [`ModalResponseData`] submission
+pub struct MissingFieldError(String);
+
+impl fmt::Display for MissingFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Modal submission is missing expected field '{}'!", self.0)
+    }
+}
+
+impl Error for MissingFieldError {}
+
 #[cfg(feature = "builder")]
 impl Builder<Modal> for ModalBuilder {
     type Error = ModalConversionError;
@@ -120,3 +178,57 @@ impl Builder<Modal> for ModalBuilder {
         return Ok(self.obj);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_input(custom_id: &str, value: &str) -> MessageComponent {
+        MessageComponent::TextInput {
+            custom_id: custom_id.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn get_finds_value_by_custom_id() {
+        let data = ModalResponseData {
+            components: vec![text_input("name", "Hugo")],
+        };
+        assert_eq!(data.get("name"), Some("Hugo"));
+    }
+
+    #[test]
+    fn get_flattens_nested_action_rows() {
+        let data = ModalResponseData {
+            components: vec![MessageComponent::ActionRow {
+                components: vec![text_input("name", "Hugo")],
+            }],
+        };
+        assert_eq!(data.get("name"), Some("Hugo"));
+    }
+
+    #[test]
+    fn get_returns_none_for_missing_custom_id() {
+        let data = ModalResponseData {
+            components: vec![text_input("name", "Hugo")],
+        };
+        assert_eq!(data.get("nickname"), None);
+    }
+
+    #[test]
+    fn required_errors_on_missing_custom_id() {
+        let data = ModalResponseData {
+            components: vec![text_input("name", "Hugo")],
+        };
+        assert!(data.required("nickname").is_err());
+    }
+
+    #[test]
+    fn required_returns_value_when_present() {
+        let data = ModalResponseData {
+            components: vec![text_input("name", "Hugo")],
+        };
+        assert_eq!(data.required("name").unwrap(), "Hugo");
+    }
+}